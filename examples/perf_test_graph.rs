@@ -1,6 +1,7 @@
 use log::info;
 use std::env::set_var;
 use tpc_graph_exec_rs::connect_nodes;
+use tpc_graph_exec_rs::graph::Graph;
 use tpc_graph_exec_rs::node::{Node, NodeInstance};
 
 // use faster/smaller `mimalloc` allocator
@@ -52,23 +53,29 @@ fn main() {
     unsafe { set_var("RUST_LOG", "INFO") };
     env_logger::init();
 
-    let mut source_node = NodeInstance::new("source".to_string(), SourceNode {}, Some(6));
-    let mut mult_node =
-        NodeInstance::new("mult x3".to_string(), MultiplierNode { factor: 3 }, Some(7));
-    let mut sink_node = NodeInstance::new("sink".to_string(), SinkNode {}, Some(8));
+    let mut graph = Graph::new();
+
+    // Ask the topology probe to place these three adjacent stages on cache-sharing cores
+    // instead of guessing core numbers by hand; falls back to no affinity for any stage the
+    // probe couldn't place (e.g. fewer usable cores than requested, or discovery unavailable).
+    let placement = graph.auto_place(3);
+    let core_for = |stage: usize| placement.get(stage).copied();
+
+    let mut source_node = NodeInstance::new("source".to_string(), SourceNode {}, core_for(0));
+    let mut mult_node = NodeInstance::new(
+        "mult x3".to_string(),
+        MultiplierNode { factor: 3 },
+        core_for(1),
+    );
+    let mut sink_node = NodeInstance::new("sink".to_string(), SinkNode {}, core_for(2));
 
     connect_nodes!(source_node -> mult_node, 16);
     connect_nodes!(mult_node -> sink_node, 16);
 
-    let threads = vec![
-        source_node.spawn().unwrap(),
-        mult_node.spawn().unwrap(),
-        sink_node.spawn().unwrap(),
-    ];
-
-    for tdx in threads {
-        tdx.join().unwrap();
-    }
+    graph.add_handle(source_node.spawn().unwrap());
+    graph.add_handle(mult_node.spawn().unwrap());
+    graph.add_handle(sink_node.spawn().unwrap());
+    graph.wait();
 
     info!("Graph processing completed!");
 }
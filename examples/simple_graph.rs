@@ -1,23 +1,9 @@
 use std::{thread, time};
 
-use crossbeam_channel::bounded;
 use log::info;
+use tpc_graph_exec_rs::connect_nodes;
 use tpc_graph_exec_rs::node::{Node, NodeInstance};
 
-/// Example node that multiplies input numbers by a given factor
-struct MultiplierNode {
-    factor: i32,
-}
-
-impl Node for MultiplierNode {
-    type Input = i32;
-    type Output = i32;
-
-    fn process(&mut self, input: Option<Self::Input>) -> Option<Self::Output> {
-        Some(input.unwrap() * self.factor)
-    }
-}
-
 /// Example node that filters even numbers
 struct FilterEvenNode;
 
@@ -66,18 +52,19 @@ impl Node for SourceNode {
 fn main() {
     env_logger::init();
 
-    let mut source_node = NodeInstance::new("source".to_string(), SourceNode { cntr: 0 });
-    let mut print_node = NodeInstance::new("printer".to_string(), PrinterNode {});
-
-    let (tx, rx) = bounded(5);
+    let mut source_node = NodeInstance::new("source".to_string(), SourceNode { cntr: 0 }, None);
+    let mut filter_node = NodeInstance::new("filter_even".to_string(), FilterEvenNode, None);
+    let mut print_node = NodeInstance::new("printer".to_string(), PrinterNode {}, None);
 
-    source_node.set_sender(tx);
-    print_node.set_receiver(rx);
+    connect_nodes!(source_node -> filter_node, 5);
+    connect_nodes!(filter_node -> print_node, 5);
 
     let source_tdx = source_node.spawn();
+    let filter_tdx = filter_node.spawn();
     let print_tdx = print_node.spawn();
 
     source_tdx.unwrap().join().unwrap();
+    filter_tdx.unwrap().join().unwrap();
     print_tdx.unwrap().join().unwrap();
 
     info!("Graph processing completed!");
@@ -1,8 +1,16 @@
-use crate::node::NodeHandle;
+//! # Graph
+//!
+//! Thin bookkeeping around a pipeline's node threads: [`crate::node::NodeInstance`], [`crate::parallel::ParallelNode`]
+//! and [`crate::periodic::PeriodicNode`] all return a plain `thread::JoinHandle<()>` from their
+//! `spawn()`, so [`Graph`] just collects those handles and joins them together, plus exposes
+//! [`Topology`]-based core placement so callers don't have to reach for `Topology::global()`
+//! themselves.
+use crate::topology::Topology;
+use std::thread::JoinHandle;
 
-/// Graph structure to manage nodes and their connections
+/// Graph structure to manage a pipeline's spawned node threads and their placement
 pub struct Graph {
-    handles: Vec<NodeHandle>,
+    handles: Vec<JoinHandle<()>>,
 }
 
 impl Graph {
@@ -12,13 +20,23 @@ impl Graph {
         }
     }
 
-    pub fn add_handle(&mut self, handle: NodeHandle) {
+    /// Track a node's spawned thread so it's joined by [`Graph::wait`]
+    pub fn add_handle(&mut self, handle: JoinHandle<()>) {
         self.handles.push(handle);
     }
 
+    /// Suggest `count` core numbers for a chain of adjacent pipeline stages, preferring cores
+    /// that the one-time [`Topology::global`] probe found share a cache domain (see that probe's
+    /// docs for what it can and can't detect). Feed the result to each node's `cpu_core`
+    /// constructor argument; this only suggests placement, it doesn't pin anything itself.
+    pub fn auto_place(&self, count: usize) -> Vec<usize> {
+        Topology::global().auto_place(count)
+    }
+
+    /// Block until every node thread added via `add_handle` has stopped
     pub fn wait(self) {
         for handle in self.handles {
-            handle.join();
+            handle.join().expect("node thread panicked");
         }
     }
 }
@@ -1,4 +1,8 @@
+pub mod graph;
 pub mod node;
+pub mod parallel;
+pub mod periodic;
+pub mod topology;
 
 /// Connect nodes together using SPSC queue and a given bounded capacity.
 /// Usage:
@@ -7,8 +11,9 @@ pub mod node;
 macro_rules! connect_nodes {
     ( $tx:ident -> $rx:ident, $size:literal ) => {
         let (tx, rx) = rtrb::RingBuffer::new($size);
-        $tx.set_sender(tx);
-        $rx.set_receiver(rx);
+        let park = $crate::node::ParkPair::new();
+        $tx.set_sender(tx, park.clone());
+        $rx.set_receiver(rx, park);
     };
 }
 
@@ -7,12 +7,21 @@
 //! node to only have a single input and output type specification with no added synchronization
 //! logic. Bounded queues are used for lock-free, cache-friendly, performant operation.
 //!
+//! A node may fan in from several upstream producers of the same `Input` type by registering more
+//! than one SPSC channel via `add_receiver` (see [`SelectPolicy`] for how they're polled), turning
+//! strictly linear pipelines into general merge/join DAGs.
+//!
+//! Symmetrically, a node may fan out (tee) the same produced item to several downstream consumers
+//! by registering more than one SPSC channel via `add_sender` (see [`BranchPolicy`] for what
+//! happens when one branch is full while the others aren't).
+//!
 //! If you need parallelism for compute-heavy stages (like a big FFT), you're often better off with
 //! vectorization or splitting the work within a stage rather than adding queue complexity.
 use crate::format_size;
 use log::{debug, error, info, warn};
 use rtrb::{Consumer, Producer};
-use std::thread;
+use std::sync::{Arc, Mutex};
+use std::thread::{self, Thread};
 use std::time::{Duration, Instant};
 
 /// Trait for types to report their memory footprint, used in throughput telemetry
@@ -63,64 +72,336 @@ pub trait Node: Send + 'static {
     fn on_stop(&mut self) {}
 }
 
+/// Token bucket used to cap a node's output throughput to a target
+/// bytes/sec, keyed on the [`DataSize`] of whatever the node produces.
+struct TokenBucket {
+    /// Sustained rate, in bytes/sec, that tokens are refilled at
+    capacity_bps: f64,
+    /// Current available tokens (fractional bytes), refilled over time
+    tokens: f64,
+    /// Burst ceiling; tokens never accumulate past this
+    burst: f64,
+    /// Last time tokens were refilled
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(capacity_bps: f64) -> Self {
+        assert!(
+            capacity_bps > 0.0,
+            "TokenBucket rate must be positive, got {capacity_bps}"
+        );
+        TokenBucket {
+            capacity_bps,
+            tokens: capacity_bps,
+            burst: capacity_bps,
+            last_refill: Instant::now(),
+        }
+    }
+
+    /// Refill tokens based on elapsed time, then block (sleep) until enough
+    /// tokens are available to cover `cost` bytes. The achieved rate is
+    /// folded into the node's existing throughput telemetry by the caller.
+    fn throttle(&mut self, cost: f64) {
+        self.tokens =
+            (self.tokens + self.capacity_bps * self.last_refill.elapsed().as_secs_f64())
+                .min(self.burst);
+        self.last_refill = Instant::now();
+
+        if self.tokens < cost {
+            let deficit = cost - self.tokens;
+            let wait = Duration::from_secs_f64(deficit / self.capacity_bps);
+            thread::sleep(wait);
+            self.tokens = cost;
+            self.last_refill = Instant::now();
+        }
+
+        self.tokens -= cost;
+    }
+}
+
+/// How a node's spawn loop waits when its input is empty or its output is
+/// full. The default `SpinOnly` behavior is only optimal when a dedicated
+/// core is guaranteed (busy-spinning burns a full core while idle); `Park`
+/// trades a little wakeup latency for letting the core sleep.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BackoffPolicy {
+    /// Spin and yield indefinitely; lowest latency, burns a full core while idle
+    #[default]
+    SpinOnly,
+    /// Spin briefly, then yield, then park the thread until its SPSC
+    /// counterpart wakes it (or a timeout elapses as a lost-wakeup backstop)
+    Park,
+}
+
+/// Thread-parking handshake shared by both ends of one SPSC channel, so a
+/// `BackoffPolicy::Park` node can sleep instead of spinning without losing a
+/// wakeup: the producer calls `wake_consumer()` right after a successful
+/// `push` (new data may be waiting for it), and the consumer calls
+/// `wake_producer()` right after a successful `pop` (space was just freed).
+#[derive(Default)]
+pub struct ParkPair {
+    producer_thread: Mutex<Option<Thread>>,
+    consumer_thread: Mutex<Option<Thread>>,
+}
+
+impl ParkPair {
+    /// Create a new, unregistered handshake for one SPSC channel
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self::default())
+    }
+
+    pub(crate) fn register_producer(&self) {
+        *self.producer_thread.lock().expect("ParkPair poisoned") = Some(thread::current());
+    }
+
+    pub(crate) fn register_consumer(&self) {
+        *self.consumer_thread.lock().expect("ParkPair poisoned") = Some(thread::current());
+    }
+
+    pub(crate) fn wake_producer(&self) {
+        if let Some(t) = self.producer_thread.lock().expect("ParkPair poisoned").as_ref() {
+            t.unpark();
+        }
+    }
+
+    pub(crate) fn wake_consumer(&self) {
+        if let Some(t) = self.consumer_thread.lock().expect("ParkPair poisoned").as_ref() {
+            t.unpark();
+        }
+    }
+}
+
+/// Upper bound on how long a parked thread will sleep before waking on its
+/// own to re-check the channel, guarding against a missed `unpark()` call
+/// (e.g. registered after the wakeup already fired) stalling the node forever.
+const PARK_TIMEOUT: Duration = Duration::from_millis(1);
+
+/// Three-phase wait used by a node's spawn loop when its input is empty or
+/// output is full: a short bounded spin (doubling each miss), then
+/// `thread::yield_now()`, and finally (under `BackoffPolicy::Park`) parking
+/// the thread until its counterpart wakes it.
+struct Backoff {
+    policy: BackoffPolicy,
+    misses: u32,
+}
+
+impl Backoff {
+    /// Number of misses spent doubling `spin_loop()` hints before falling
+    /// through to `yield_now()`
+    const SPIN_MISS_LIMIT: u32 = 10;
+    /// Total misses (spin phase included) after which `Park` starts parking;
+    /// the misses between `SPIN_MISS_LIMIT` and this are spent yielding
+    const YIELD_MISS_LIMIT: u32 = 20;
+
+    fn new(policy: BackoffPolicy) -> Self {
+        Backoff { policy, misses: 0 }
+    }
+
+    /// Back off once after a failed pop/push attempt
+    fn wait(&mut self) {
+        if self.misses < Self::SPIN_MISS_LIMIT {
+            for _ in 0..(1u32 << self.misses) {
+                std::hint::spin_loop();
+            }
+        } else if self.policy == BackoffPolicy::SpinOnly || self.misses < Self::YIELD_MISS_LIMIT {
+            thread::yield_now();
+        } else {
+            thread::park_timeout(PARK_TIMEOUT);
+        }
+        // Saturate rather than overflow: a node left idle under `SpinOnly` for long enough
+        // would otherwise panic (debug) / wrap (release) once `misses` exceeds `u32::MAX`.
+        // Past `YIELD_MISS_LIMIT` the exact count no longer affects which phase we're in, so
+        // there's nothing lost by capping it.
+        self.misses = self.misses.saturating_add(1);
+    }
+
+    /// Reset the miss counter, e.g. right after data was found / space freed
+    fn reset(&mut self) {
+        self.misses = 0;
+    }
+}
+
+/// Strategy for picking which input channel to poll next when a node has more
+/// than one, e.g. in a merge/join topology fed by several upstream nodes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SelectPolicy {
+    /// Rotate the starting index each iteration so no producer is starved
+    /// (fair scheduling across all registered receivers)
+    #[default]
+    RoundRobin,
+    /// Always scan starting from index 0, so lower-index receivers (added
+    /// first via `add_receiver`) are serviced ahead of later ones whenever
+    /// they have data ready
+    Priority,
+    /// Alias of `Priority` for call sites that want to name the behavior
+    /// explicitly (always prefer the lowest-index receiver with data)
+    PreferLowestIndex,
+}
+
+/// Receiver side of one input channel, paired with the handshake used to wake
+/// its upstream producer under `BackoffPolicy::Park`.
+struct InputChannel<I> {
+    rx: Consumer<I>,
+    park: Arc<ParkPair>,
+}
+
+/// What a node does with a fan-out branch that's full when the others aren't.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BranchPolicy {
+    /// Yield (per `backoff_policy`) until every branch has space, so no
+    /// downstream consumer ever misses an item
+    #[default]
+    BlockAll,
+    /// Skip a full branch this item and count the drop, so one slow
+    /// consumer can't stall delivery to the others
+    DropSlowest,
+}
+
+/// Sender side of one output branch, paired with the handshake used to wake
+/// its downstream consumer under `BackoffPolicy::Park`.
+struct OutputChannel<O> {
+    tx: Producer<O>,
+    park: Arc<ParkPair>,
+    /// Set once this branch's consumer is observed abandoned; the branch is
+    /// kept (not removed) so its index is stable for telemetry, but is
+    /// skipped on every future push
+    retired: bool,
+    /// Items dropped on this branch per telemetry period under `DropSlowest`
+    drop_cntr: u64,
+}
+
 /// A node instance in the graph with its channels
 pub struct NodeInstance<I, O> {
-    /// Receiver side of channel for Node's input
-    input_rx: Option<Consumer<I>>,
-    /// Sender side of channel for Node's output
-    output_tx: Option<Producer<O>>,
+    /// Receiver side(s) of channel(s) for Node's input. More than one entry
+    /// means this node fans in from several upstream producers of the same
+    /// `Input` type.
+    input_rxs: Vec<InputChannel<I>>,
+    /// Policy used to pick which input channel to poll next when there are
+    /// multiple
+    select_policy: SelectPolicy,
+    /// Next index to start scanning from for `SelectPolicy::RoundRobin`
+    rr_idx: usize,
+    /// Sender side(s) of channel(s) for Node's output. More than one entry
+    /// means this node fans out (tees) the same item to several downstream
+    /// consumers.
+    output_txs: Vec<OutputChannel<O>>,
+    /// Policy used to handle a full branch when fanning out to more than one
+    branch_policy: BranchPolicy,
     /// Node name, used in naming OS thread
     name: String,
     /// Node to instantiate in spawned thread
     node: Box<dyn Node<Input = I, Output = O>>,
     /// Optional CPU core to pin spawned thread to
     cpu_core: Option<usize>,
+    /// How the spawn loop waits when input is empty or output is full
+    backoff_policy: BackoffPolicy,
     /// Bytes processed per telemetry period
     bytes_processed_cntr: u64,
+    /// Optional output rate limit (token bucket), set via `with_rate_limit`
+    rate_limit: Option<TokenBucket>,
+    /// Bytes actually pushed per telemetry period while rate limited, to
+    /// compare requested-vs-observed throughput
+    rate_limited_bytes_cntr: u64,
 }
 
-impl<I: Send + 'static + DataSize, O: Clone + Send + 'static> NodeInstance<I, O> {
+impl<I: Send + 'static + DataSize, O: Clone + Send + 'static + DataSize> NodeInstance<I, O> {
     /// Create a new NodeInstance with a given name and Node
     pub fn new<N>(name: String, node: N, cpu_core: Option<usize>) -> Self
     where
         N: Node<Input = I, Output = O>,
     {
         NodeInstance {
-            input_rx: None,
-            output_tx: None,
+            input_rxs: Vec::new(),
+            select_policy: SelectPolicy::default(),
+            rr_idx: 0,
+            output_txs: Vec::new(),
+            branch_policy: BranchPolicy::default(),
             name,
             node: Box::new(node),
             cpu_core,
+            backoff_policy: BackoffPolicy::default(),
             bytes_processed_cntr: 0,
+            rate_limit: None,
+            rate_limited_bytes_cntr: 0,
         }
     }
 
-    /// Set input receiver channel
-    pub fn set_receiver(&mut self, rx: Consumer<I>) {
-        self.input_rx = Some(rx);
+    /// Cap this node's output to `bytes_per_sec`, useful for replaying
+    /// captured traffic at a realistic rate or preventing one fast stage
+    /// from starving others downstream. Panics if `bytes_per_sec` isn't
+    /// positive, since a zero or negative rate can never be throttled to.
+    pub fn with_rate_limit(mut self, bytes_per_sec: f64) -> Self {
+        self.rate_limit = Some(TokenBucket::new(bytes_per_sec));
+        self
     }
 
-    /// Set output transmitter channel
-    pub fn set_sender(&mut self, tx: Producer<O>) {
-        self.output_tx = Some(tx);
+    /// Use a non-default strategy for polling multiple input channels (see
+    /// [`SelectPolicy`]); has no effect with a single input.
+    pub fn with_select_policy(mut self, policy: SelectPolicy) -> Self {
+        self.select_policy = policy;
+        self
+    }
+
+    /// Use a non-default wait strategy when input is empty or output is full
+    /// (see [`BackoffPolicy`]); spin-only remains the default since it's only
+    /// suboptimal when a dedicated core isn't guaranteed for this node.
+    pub fn with_backoff_policy(mut self, policy: BackoffPolicy) -> Self {
+        self.backoff_policy = policy;
+        self
+    }
+
+    /// Use a non-default policy for handling a full branch when fanning out
+    /// to more than one downstream consumer (see [`BranchPolicy`]).
+    pub fn with_branch_policy(mut self, policy: BranchPolicy) -> Self {
+        self.branch_policy = policy;
+        self
+    }
+
+    /// Set input receiver channel, replacing any previously registered ones.
+    /// Prefer `add_receiver` when fanning in from more than one producer.
+    pub fn set_receiver(&mut self, rx: Consumer<I>, park: Arc<ParkPair>) {
+        self.input_rxs.clear();
+        self.input_rxs.push(InputChannel { rx, park });
+    }
+
+    /// Register an additional input receiver channel, turning this node into
+    /// a fan-in merge point polled per `select_policy`.
+    pub fn add_receiver(&mut self, rx: Consumer<I>, park: Arc<ParkPair>) {
+        self.input_rxs.push(InputChannel { rx, park });
+    }
+
+    /// Set output transmitter channel, replacing any previously registered
+    /// ones. Prefer `add_sender` when fanning out (tee-ing) to more than one
+    /// downstream consumer.
+    pub fn set_sender(&mut self, tx: Producer<O>, park: Arc<ParkPair>) {
+        self.output_txs.clear();
+        self.output_txs.push(OutputChannel { tx, park, retired: false, drop_cntr: 0 });
+    }
+
+    /// Register an additional output sender channel, turning this node into
+    /// a fan-out (tee) point cloning every item across branches per
+    /// `branch_policy`.
+    pub fn add_sender(&mut self, tx: Producer<O>, park: Arc<ParkPair>) {
+        self.output_txs.push(OutputChannel { tx, park, retired: false, drop_cntr: 0 });
     }
 
     /// Spawn and start new OS thread with Node logic, returning handle to thread
     pub fn spawn(mut self) -> Result<thread::JoinHandle<()>, std::io::Error> {
-        if self.input_rx.is_none() {
+        if self.input_rxs.is_empty() {
             warn!(
                 "No input (RX) channel connected to node '{}' (this may be intentional)",
                 self.name
             );
         }
-        if self.output_tx.is_none() {
+        if self.output_txs.is_empty() {
             warn!(
                 "No output (TX) channel connected to node '{}' (this may be intentional)",
                 self.name
             );
         }
 
-        if self.output_tx.is_none() && self.input_rx.is_none() {
+        if self.output_txs.is_empty() && self.input_rxs.is_empty() {
             error!(
                 "Both input and output channels of node '{}' are missing! Thread will spawn a Node process with no data connections.",
                 self.name
@@ -140,6 +421,15 @@ impl<I: Send + 'static + DataSize, O: Clone + Send + 'static> NodeInstance<I, O>
 
             };
 
+            // Register this thread as the consumer/producer side of each channel's
+            // park handshake so the other end can `unpark()` us under `Park`.
+            for input_ch in &self.input_rxs {
+                input_ch.park.register_consumer();
+            }
+            for output_ch in &self.output_txs {
+                output_ch.park.register_producer();
+            }
+
             info!("Node '{}' starting", self.name);
             self.node.on_start();
 
@@ -149,27 +439,66 @@ impl<I: Send + 'static + DataSize, O: Clone + Send + 'static> NodeInstance<I, O>
             let mut recv_time_acc = 0;
             let mut proc_time_acc = 0;
             let mut send_time_acc = 0;
+            let mut recv_backoff = Backoff::new(self.backoff_policy);
+            let mut send_backoff = Backoff::new(self.backoff_policy);
 
             'main_loop: loop {
-                let node_output = if let Some(input_ch) = self.input_rx.as_mut() {
-                    // There exists some input channel for us to poll for new input data.
-                    // rtrb's pop() is non-blocking, so we loop until data is available or the
-                    // channel is closed (producer dropped). The OS scheduler puts the thread to
-                    // sleep during yield when no data available and wakes it when scheduled.
+                let node_output = if !self.input_rxs.is_empty() {
+                    // There exist input channel(s) for us to poll for new input data.
+                    // rtrb's pop() is non-blocking, so we loop until data is available on some
+                    // channel or every channel is closed (all producers dropped). Meanwhile we
+                    // back off per `backoff_policy` so an idle node doesn't necessarily burn a
+                    // full core spinning.
                     let recv_time = Instant::now();
                     let rx_data = loop {
-                        match input_ch.pop() {
-                            Ok(data) => break data,
-                            Err(_) => {
-                                // Queue is empty - check if producer is still alive
-                                if input_ch.is_abandoned() {
-                                    // Producer dropped, channel is closed
-                                    break 'main_loop;
+                        if self.input_rxs.is_empty() {
+                            // Every registered input was individually abandoned
+                            break 'main_loop;
+                        }
+
+                        let n = self.input_rxs.len();
+                        let start = match self.select_policy {
+                            SelectPolicy::RoundRobin => {
+                                let start = self.rr_idx;
+                                self.rr_idx = (self.rr_idx + 1) % n;
+                                start
+                            }
+                            SelectPolicy::Priority | SelectPolicy::PreferLowestIndex => 0,
+                        };
+
+                        let mut abandoned_idx = None;
+                        let mut popped = None;
+                        for offset in 0..n {
+                            let idx = (start + offset) % n;
+                            match self.input_rxs[idx].rx.pop() {
+                                Ok(data) => {
+                                    popped = Some((idx, data));
+                                    break;
+                                }
+                                Err(_) => {
+                                    if self.input_rxs[idx].rx.is_abandoned() {
+                                        abandoned_idx = Some(idx);
+                                    }
                                 }
-                                // Queue just empty, yield to scheduler and try again
-                                thread::yield_now();
                             }
                         }
+
+                        if let Some((idx, data)) = popped {
+                            // Space was just freed on this channel; wake a parked upstream
+                            // producer waiting on it.
+                            self.input_rxs[idx].park.wake_producer();
+                            recv_backoff.reset();
+                            break data;
+                        }
+                        if let Some(idx) = abandoned_idx {
+                            // Drop one abandoned channel per pass; the node stays alive until
+                            // every input has been individually abandoned
+                            self.input_rxs.remove(idx);
+                            self.rr_idx = self.rr_idx.min(self.input_rxs.len().saturating_sub(1));
+                            continue;
+                        }
+                        // All channels still alive but empty, back off and try again
+                        recv_backoff.wait();
                     };
                     recv_time_acc += recv_time.elapsed().as_nanos();
                     self.bytes_processed_cntr += rx_data.data_size() as u64;
@@ -187,27 +516,71 @@ impl<I: Send + 'static + DataSize, O: Clone + Send + 'static> NodeInstance<I, O>
                 // data, or thread wait/sleep/yield has already occurred in above RX loops
                 if let Some(tx_data) = node_output {
                     let send_time = Instant::now();
-                    let output_ch = self.output_tx
-                        .as_mut()
-                        .expect("node produced data, so output channel should be connected, to not black-hole data");
 
-                    // rtrb's push() is non-blocking, so we loop until space is available or the
-                    // channel is closed (consumer dropped). On failure, push() returns the value.
-                    let mut data = tx_data;
+                    if self.output_txs.iter().all(|b| b.retired) {
+                        panic!("node '{}' produced data, so output channel should be connected, to not black-hole data", self.name);
+                    }
+
+                    if let Some(bucket) = self.rate_limit.as_mut() {
+                        bucket.throttle(tx_data.data_size() as f64);
+                        self.rate_limited_bytes_cntr += tx_data.data_size() as u64;
+                    }
+
+                    // Clone the item into every active branch but the last (which takes
+                    // ownership), then push to each. rtrb's push() is non-blocking, so we retry a
+                    // branch until it has space or its consumer is gone, per `branch_policy`.
+                    let last_active = self.output_txs.iter().rposition(|b| !b.retired);
+                    let mut pending: Vec<Option<O>> = self
+                        .output_txs
+                        .iter()
+                        .enumerate()
+                        .map(|(idx, b)| (!b.retired && Some(idx) != last_active).then(|| tx_data.clone()))
+                        .collect();
+                    if let Some(idx) = last_active {
+                        pending[idx] = Some(tx_data);
+                    }
+
                     loop {
-                        match output_ch.push(data) {
-                            Ok(_) => break,
-                            Err(rtrb::PushError::Full(returned_data)) => {
-                                data = returned_data;
-                                // Queue is full - check if consumer is still alive
-                                if output_ch.is_abandoned() {
-                                    // Consumer dropped, channel is closed
-                                    break 'main_loop;
+                        let mut all_done = true;
+                        for (idx, branch) in self.output_txs.iter_mut().enumerate() {
+                            let Some(data) = pending[idx].take() else {
+                                continue;
+                            };
+                            match branch.tx.push(data) {
+                                Ok(_) => {
+                                    // New data is waiting; wake a parked downstream consumer.
+                                    branch.park.wake_consumer();
+                                }
+                                Err(rtrb::PushError::Full(returned_data)) => {
+                                    if branch.tx.is_abandoned() {
+                                        branch.retired = true;
+                                        warn!(
+                                            "Branch {} of node '{}' abandoned; no longer broadcasting to it",
+                                            idx, self.name
+                                        );
+                                    } else {
+                                        match self.branch_policy {
+                                            BranchPolicy::BlockAll => {
+                                                pending[idx] = Some(returned_data);
+                                                all_done = false;
+                                            }
+                                            BranchPolicy::DropSlowest => {
+                                                branch.drop_cntr += 1;
+                                            }
+                                        }
+                                    }
                                 }
-                                // Queue just full, yield to scheduler and try again
-                                thread::yield_now();
                             }
                         }
+                        if self.output_txs.iter().all(|b| b.retired) {
+                            // Every downstream branch gone, nothing left to feed
+                            break 'main_loop;
+                        }
+                        if all_done {
+                            send_backoff.reset();
+                            break;
+                        }
+                        send_backoff.wait();
                     }
                     send_time_acc += send_time.elapsed().as_nanos();
                 }
@@ -218,15 +591,44 @@ impl<I: Send + 'static + DataSize, O: Clone + Send + 'static> NodeInstance<I, O>
                     let percent_proc = 100.0 * (proc_time_acc as f32) / total_time_ns;
                     let percent_send = 100.0 * (send_time_acc as f32) / total_time_ns;
 
-                    info!("{} recv() throughput: {}/sec | RX wait: {:.2}%, Process wait: {:.2}%, TX wait: {:.2}%",
-                        self.name,
-                        format_size(self.bytes_processed_cntr as f32),
-                        percent_recv,
-                        percent_proc,
-                        percent_send
-                    );
+                    if let Some(bucket) = self.rate_limit.as_ref() {
+                        info!("{} recv() throughput: {}/sec | RX wait: {:.2}%, Process wait: {:.2}%, TX wait: {:.2}% | rate limit: requested {}/sec, observed {}/sec",
+                            self.name,
+                            format_size(self.bytes_processed_cntr as f32),
+                            percent_recv,
+                            percent_proc,
+                            percent_send,
+                            format_size(bucket.capacity_bps as f32),
+                            format_size(self.rate_limited_bytes_cntr as f32),
+                        );
+                    } else {
+                        info!("{} recv() throughput: {}/sec | RX wait: {:.2}%, Process wait: {:.2}%, TX wait: {:.2}%",
+                            self.name,
+                            format_size(self.bytes_processed_cntr as f32),
+                            percent_recv,
+                            percent_proc,
+                            percent_send
+                        );
+                    }
+
+                    if self.output_txs.len() > 1 {
+                        for (idx, branch) in self.output_txs.iter_mut().enumerate() {
+                            if branch.retired {
+                                continue;
+                            }
+                            let capacity = branch.tx.buffer().capacity();
+                            let fullness = 100.0
+                                * (1.0 - (branch.tx.slots() as f32 / capacity as f32));
+                            info!(
+                                "{} branch {}: {:.2}% full, {} dropped this period",
+                                self.name, idx, fullness, branch.drop_cntr
+                            );
+                            branch.drop_cntr = 0;
+                        }
+                    }
 
                     self.bytes_processed_cntr = 0;
+                    self.rate_limited_bytes_cntr = 0;
                     telem_time = Instant::now();
                     recv_time_acc = 0;
                     proc_time_acc = 0;
@@ -239,3 +641,105 @@ impl<I: Send + 'static + DataSize, O: Clone + Send + 'static> NodeInstance<I, O>
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rtrb::RingBuffer;
+
+    struct IdentityNode;
+    impl Node for IdentityNode {
+        type Input = i64;
+        type Output = i64;
+        fn process(&mut self, input: Option<i64>) -> Option<i64> {
+            input
+        }
+    }
+
+    #[test]
+    fn round_robin_select_policy_is_fair_across_inputs() {
+        // Three inputs, each pre-loaded with items tagged `channel * 1_000_000 + seq`. While
+        // all three still have data, `RoundRobin` must visit them in strict rotation starting
+        // from wherever it left off, so no producer is starved in favor of another.
+        const CHANNELS: i64 = 3;
+        const PER_CHANNEL: i64 = 50;
+
+        let mut node = NodeInstance::new("rr_test".to_string(), IdentityNode, None)
+            .with_select_policy(SelectPolicy::RoundRobin);
+        for channel in 0..CHANNELS {
+            let (mut tx, rx) = RingBuffer::new(PER_CHANNEL as usize);
+            for seq in 0..PER_CHANNEL {
+                tx.push(channel * 1_000_000 + seq).unwrap();
+            }
+            drop(tx); // all items are already buffered; the node can drain them standalone
+            node.add_receiver(rx, ParkPair::new());
+        }
+        let (out_tx, mut out_rx) = RingBuffer::new((CHANNELS * PER_CHANNEL) as usize);
+        node.set_sender(out_tx, ParkPair::new());
+
+        let handle = node.spawn().expect("failed to spawn node");
+        handle.join().expect("node thread panicked");
+
+        let mut received = Vec::new();
+        while let Ok(v) = out_rx.pop() {
+            received.push(v);
+        }
+
+        assert_eq!(received.len(), (CHANNELS * PER_CHANNEL) as usize);
+        for (i, v) in received.iter().enumerate() {
+            let expected_channel = i as i64 % CHANNELS;
+            let expected_seq = i as i64 / CHANNELS;
+            assert_eq!(
+                *v,
+                expected_channel * 1_000_000 + expected_seq,
+                "item {i} broke round-robin fairness"
+            );
+        }
+    }
+
+    #[test]
+    fn branch_policy_drop_slowest_never_blocks_on_a_stalled_branch() {
+        // drop_cntr itself is private telemetry state reset every 1-second period, so this
+        // exercises the observable effect of drop accounting instead: a stalled branch only
+        // ever holds its own buffer's worth of items, and a fast sibling branch is never held
+        // up waiting for it.
+        const ITEMS: i64 = 20;
+        const SLOW_CAPACITY: usize = 4;
+
+        let (mut in_tx, in_rx) = RingBuffer::new(ITEMS as usize);
+        for i in 0..ITEMS {
+            in_tx.push(i).unwrap();
+        }
+        drop(in_tx);
+
+        let mut node = NodeInstance::new("drop_slowest_test".to_string(), IdentityNode, None)
+            .with_branch_policy(BranchPolicy::DropSlowest);
+        node.set_receiver(in_rx, ParkPair::new());
+        let (fast_tx, mut fast_rx) = RingBuffer::new(ITEMS as usize);
+        node.add_sender(fast_tx, ParkPair::new());
+        let (slow_tx, mut slow_rx) = RingBuffer::new(SLOW_CAPACITY);
+        node.add_sender(slow_tx, ParkPair::new());
+
+        let handle = node.spawn().expect("failed to spawn node");
+
+        // Keep draining the fast branch so it never backs up; the slow branch is never read,
+        // simulating a downstream consumer that's stalled.
+        let mut fast_received = Vec::new();
+        while (fast_received.len() as i64) < ITEMS {
+            match fast_rx.pop() {
+                Ok(v) => fast_received.push(v),
+                Err(_) => thread::yield_now(),
+            }
+        }
+        handle.join().expect("node thread panicked");
+
+        assert_eq!(fast_received, (0..ITEMS).collect::<Vec<_>>());
+
+        let slow_received: Vec<i64> = std::iter::from_fn(|| slow_rx.pop().ok()).collect();
+        assert_eq!(slow_received.len(), SLOW_CAPACITY);
+        assert_eq!(
+            slow_received,
+            (0..SLOW_CAPACITY as i64).collect::<Vec<_>>()
+        );
+    }
+}
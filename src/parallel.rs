@@ -0,0 +1,412 @@
+//! # Parallel Node
+//!
+//! The [`crate::node`] docs note that compute-heavy stages are often better off
+//! "splitting the work within a stage" rather than adding queue complexity. This
+//! module provides that split: [`ParallelNode`] fans a single SPSC input stream
+//! across `width` worker threads and re-joins their output in strict input order,
+//! so a downstream node never observes reordered data even though the work
+//! inside the stage ran concurrently.
+//!
+//! Internally this is a fork-join pipeline: a dispatcher thread pops each input
+//! item, tags it with a monotonically increasing sequence number, and hands it
+//! to a pool of workers over a bounded MPMC work queue. Each worker clones the
+//! user's [`Node`] once at startup and runs `process()` independently. Workers
+//! send their `(seq, output)` pairs to a single collector, which only forwards
+//! to the real output channel while the next expected sequence number is at the
+//! head of its stash, buffering out-of-order arrivals in between. A worker
+//! still reports a seq whose `process()` returned `None` (e.g. a filtering
+//! node) — with no output attached — so the collector can step `next_seq` past
+//! the hole instead of stalling on a sequence number that will never arrive.
+use crate::format_size;
+use crate::node::{DataSize, Node, ParkPair};
+use crossbeam_channel::bounded;
+use log::{debug, info, warn};
+use rtrb::{Consumer, Producer};
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::sync::atomic::{AtomicI64, AtomicUsize, Ordering as AtomicOrdering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How many in-flight (dispatched but not yet emitted) items the dispatcher will
+/// allow before stalling, expressed as a multiple of `width`. Bounds the
+/// collector's reorder stash so one slow worker can't let memory grow
+/// unbounded while the rest of the pool keeps racing ahead.
+const STASH_BACKPRESSURE_FACTOR: i64 = 2;
+
+/// A sequence-tagged item moving through the internal work queue and collector.
+struct Seqd<T> {
+    seq: u64,
+    item: T,
+}
+
+// Ordered for use in the collector's `BinaryHeap` stash, lowest `seq` first
+// (the heap is wrapped to act as a min-heap via `Reverse`-style inversion below).
+impl<T> PartialEq for Seqd<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.seq == other.seq
+    }
+}
+impl<T> Eq for Seqd<T> {}
+impl<T> PartialOrd for Seqd<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl<T> Ord for Seqd<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reversed so `BinaryHeap` (a max-heap) pops the smallest sequence first.
+        other.seq.cmp(&self.seq)
+    }
+}
+
+/// Worker-pool wrapper around a [`Node`] that preserves input ordering on output.
+///
+/// `N` must be [`Clone`] since each worker thread processes with its own owned
+/// copy (no shared `&mut self` across threads).
+pub struct ParallelNode<N: Node + Clone> {
+    /// Number of worker threads to fan the input across
+    width: usize,
+    /// Node name, used in naming OS threads
+    name: String,
+    /// Node instance cloned once per worker
+    node: N,
+    /// Receiver side of channel for Node's input
+    input_rx: Option<Consumer<N::Input>>,
+    /// Park handshake for the input channel, so the dispatcher can wake a
+    /// parked upstream producer right after freeing space
+    input_park: Option<Arc<ParkPair>>,
+    /// Sender side of channel for Node's output
+    output_tx: Option<Producer<N::Output>>,
+    /// Park handshake for the output channel, so the collector can wake a
+    /// parked downstream consumer right after pushing new data
+    output_park: Option<Arc<ParkPair>>,
+}
+
+impl<N> ParallelNode<N>
+where
+    N: Node + Clone,
+{
+    /// Create a new ParallelNode with a given name, Node, and worker pool width
+    pub fn new(name: String, node: N, width: usize) -> Self {
+        assert!(width > 0, "ParallelNode '{name}' width must be at least 1");
+        ParallelNode {
+            width,
+            name,
+            node,
+            input_rx: None,
+            input_park: None,
+            output_tx: None,
+            output_park: None,
+        }
+    }
+
+    /// Set input receiver channel
+    pub fn set_receiver(&mut self, rx: Consumer<N::Input>, park: Arc<ParkPair>) {
+        self.input_rx = Some(rx);
+        self.input_park = Some(park);
+    }
+
+    /// Set output transmitter channel
+    pub fn set_sender(&mut self, tx: Producer<N::Output>, park: Arc<ParkPair>) {
+        self.output_tx = Some(tx);
+        self.output_park = Some(park);
+    }
+
+    /// Spawn the dispatcher/worker-pool/collector pipeline, returning a single
+    /// handle that joins once all internal threads have wound down.
+    pub fn spawn(mut self) -> Result<thread::JoinHandle<()>, std::io::Error>
+    where
+        N::Input: Send + 'static + DataSize,
+        N::Output: Send + 'static + DataSize,
+    {
+        let mut input_rx = self
+            .input_rx
+            .take()
+            .expect("ParallelNode requires an input (RX) channel");
+        let input_park = self
+            .input_park
+            .take()
+            .expect("ParallelNode requires an input (RX) channel");
+        let mut output_tx = self
+            .output_tx
+            .take()
+            .expect("ParallelNode requires an output (TX) channel");
+        let output_park = self
+            .output_park
+            .take()
+            .expect("ParallelNode requires an output (TX) channel");
+        let width = self.width;
+        let name = self.name.clone();
+        let node = self.node;
+
+        thread::Builder::new().name(name.clone()).spawn(move || {
+            info!("ParallelNode '{}' starting {} workers", name, width);
+            input_park.register_consumer();
+
+            // Dispatcher -> workers: bounded MPMC work queue. Each worker holds its own
+            // `Receiver` clone and blocks in `recv()` independently, so `width` workers can
+            // all be waiting on work at once instead of serializing through a shared lock.
+            // Capacity bounds how far the dispatcher can run ahead of the slowest worker
+            // picking up work.
+            let (work_tx, work_rx) = bounded::<Seqd<N::Input>>(width * 2);
+
+            // Workers -> collector: plain mpsc, naturally multi-producer single-consumer.
+            // Carries `None` for an item a worker's `process()` dropped (e.g. a filtering
+            // node), so the collector can still advance `next_seq` past the hole instead of
+            // stalling on a sequence number that will never arrive.
+            let (result_tx, result_rx) = std::sync::mpsc::channel::<Seqd<Option<N::Output>>>();
+
+            // Tracks items dispatched but not yet emitted by the collector, so the
+            // dispatcher can apply backpressure before the reorder stash grows
+            // unbounded on a slow worker.
+            let in_flight = Arc::new(AtomicI64::new(0));
+            let bytes_processed_cntr = Arc::new(AtomicUsize::new(0));
+
+            let workers: Vec<thread::JoinHandle<()>> = (0..width)
+                .map(|worker_idx| {
+                    // crossbeam's `Receiver` is already a cheap, clonable multi-consumer
+                    // handle; no extra `Arc` needed to share it across worker threads.
+                    let work_rx = work_rx.clone();
+                    let result_tx = result_tx.clone();
+                    let mut worker_node = node.clone();
+                    let worker_name = format!("{name}-w{worker_idx}");
+                    thread::Builder::new()
+                        .name(worker_name)
+                        .spawn(move || {
+                            while let Ok(Seqd { seq, item }) = work_rx.recv() {
+                                // Always report back, even when `process()` drops the item,
+                                // so the collector can advance `next_seq` past the hole
+                                // instead of stalling forever on a sequence gap.
+                                let output = worker_node.process(Some(item));
+                                if result_tx.send(Seqd { seq, item: output }).is_err() {
+                                    // Collector has gone away, nothing left to do
+                                    break;
+                                }
+                            }
+                            // `Err(_)` from `recv()` above: dispatcher dropped, work queue drained
+                        })
+                        .expect("failed to spawn ParallelNode worker thread")
+                })
+                .collect();
+            // Drop our extra sender so the workers' senders are the only ones keeping
+            // `result_rx` alive; once every worker exits, recv() on the collector side
+            // observes the channel closing.
+            drop(result_tx);
+
+            let collector_name = name.clone();
+            let collector_in_flight = Arc::clone(&in_flight);
+            let collector_bytes = Arc::clone(&bytes_processed_cntr);
+            let collector = thread::Builder::new()
+                .name(format!("{collector_name}-collect"))
+                .spawn(move || {
+                    output_park.register_producer();
+                    let mut next_seq: u64 = 0;
+                    let mut stash: BinaryHeap<Seqd<Option<N::Output>>> = BinaryHeap::new();
+                    let mut telem_time = Instant::now();
+
+                    let mut emit = |data: N::Output| {
+                        let mut data = data;
+                        loop {
+                            match output_tx.push(data) {
+                                Ok(_) => {
+                                    output_park.wake_consumer();
+                                    break true;
+                                }
+                                Err(rtrb::PushError::Full(returned)) => {
+                                    data = returned;
+                                    if output_tx.is_abandoned() {
+                                        break false;
+                                    }
+                                    thread::yield_now();
+                                }
+                            }
+                        }
+                    };
+
+                    'collect: loop {
+                        match result_rx.recv() {
+                            Ok(seqd) => {
+                                stash.push(seqd);
+                                while stash.peek().is_some_and(|s| s.seq == next_seq) {
+                                    let Seqd { item, .. } = stash.pop().unwrap();
+                                    // A worker's `process()` dropped this item (e.g. a
+                                    // filtering node returning `None`); nothing to emit, but
+                                    // `next_seq` must still advance and `in_flight` still
+                                    // decrement or every later seq stalls behind this hole.
+                                    if let Some(item) = item {
+                                        collector_bytes
+                                            .fetch_add(item.data_size(), AtomicOrdering::Relaxed);
+                                        if !emit(item) {
+                                            break 'collect;
+                                        }
+                                    }
+                                    next_seq += 1;
+                                    collector_in_flight.fetch_sub(1, AtomicOrdering::Relaxed);
+                                }
+                            }
+                            Err(_) => {
+                                // All workers have exited (input closed upstream); flush
+                                // whatever remains rather than stalling on a sequence gap
+                                // that will never arrive.
+                                while let Some(Seqd { item, .. }) = stash.pop() {
+                                    if let Some(item) = item {
+                                        if !emit(item) {
+                                            break 'collect;
+                                        }
+                                    }
+                                    collector_in_flight.fetch_sub(1, AtomicOrdering::Relaxed);
+                                }
+                                break;
+                            }
+                        }
+
+                        if telem_time.elapsed() >= Duration::from_secs(1) {
+                            info!(
+                                "{} output throughput: {}/sec",
+                                collector_name,
+                                format_size(
+                                    collector_bytes.swap(0, AtomicOrdering::Relaxed) as f32
+                                )
+                            );
+                            telem_time = Instant::now();
+                        }
+                    }
+                })
+                .expect("failed to spawn ParallelNode collector thread");
+
+            // Dispatcher loop runs on this thread: pop from the real SPSC input,
+            // tag with a sequence number, and push into the shared work queue.
+            let mut seq: u64 = 0;
+            'dispatch: loop {
+                let item = loop {
+                    match input_rx.pop() {
+                        Ok(data) => {
+                            input_park.wake_producer();
+                            break data;
+                        }
+                        Err(_) => {
+                            if input_rx.is_abandoned() {
+                                break 'dispatch;
+                            }
+                            thread::yield_now();
+                        }
+                    }
+                };
+
+                // Apply backpressure so the stash can't grow past ~2*width entries
+                // while one worker is slow to finish its item.
+                while in_flight.load(AtomicOrdering::Relaxed) >= width as i64 * STASH_BACKPRESSURE_FACTOR
+                {
+                    thread::yield_now();
+                }
+                in_flight.fetch_add(1, AtomicOrdering::Relaxed);
+
+                if work_tx.send(Seqd { seq, item }).is_err() {
+                    warn!("ParallelNode '{}' work queue closed unexpectedly", name);
+                    break;
+                }
+                seq += 1;
+            }
+            drop(work_tx); // signal workers: no more work is coming
+
+            for worker in workers {
+                worker.join().expect("ParallelNode worker thread panicked");
+            }
+            collector.join().expect("ParallelNode collector thread panicked");
+
+            debug!("ParallelNode '{}' stopping", name);
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::node::Node;
+    use rtrb::RingBuffer;
+
+    #[derive(Clone)]
+    struct DoubleNode;
+    impl Node for DoubleNode {
+        type Input = i32;
+        type Output = i32;
+        fn process(&mut self, input: Option<i32>) -> Option<i32> {
+            input.map(|v| v * 2)
+        }
+    }
+
+    /// Drops every odd input, same as the `FilterEvenNode` example in `examples/simple_graph.rs`.
+    #[derive(Clone)]
+    struct FilterEvenNode;
+    impl Node for FilterEvenNode {
+        type Input = i32;
+        type Output = i32;
+        fn process(&mut self, input: Option<i32>) -> Option<i32> {
+            input.filter(|v| v % 2 == 0)
+        }
+    }
+
+    /// Push every item through a `ParallelNode` of the given width and collect whatever comes
+    /// out the other side, in order.
+    fn run_parallel<N>(node: N, width: usize, items: Vec<i32>) -> Vec<i32>
+    where
+        N: Node<Input = i32, Output = i32> + Clone,
+    {
+        let capacity = items.len().max(1);
+        let (in_tx, in_rx) = RingBuffer::new(capacity);
+        let (out_tx, out_rx) = RingBuffer::new(capacity);
+        let mut pnode = ParallelNode::new("test".to_string(), node, width);
+        pnode.set_receiver(in_rx, ParkPair::new());
+        pnode.set_sender(out_tx, ParkPair::new());
+        let handle = pnode.spawn().expect("failed to spawn ParallelNode");
+
+        let mut in_tx = in_tx;
+        for mut item in items {
+            loop {
+                match in_tx.push(item) {
+                    Ok(_) => break,
+                    Err(rtrb::PushError::Full(returned)) => {
+                        item = returned;
+                        thread::yield_now();
+                    }
+                }
+            }
+        }
+        drop(in_tx); // signal end of input
+
+        let mut out_rx = out_rx;
+        let mut results = Vec::new();
+        loop {
+            match out_rx.pop() {
+                Ok(v) => results.push(v),
+                Err(_) => {
+                    if out_rx.is_abandoned() {
+                        break;
+                    }
+                    thread::yield_now();
+                }
+            }
+        }
+        handle.join().expect("ParallelNode thread panicked");
+        results
+    }
+
+    #[test]
+    fn preserves_input_order_across_workers() {
+        let items: Vec<i32> = (0..500).collect();
+        let expected: Vec<i32> = items.iter().map(|v| v * 2).collect();
+        assert_eq!(run_parallel(DoubleNode, 4, items), expected);
+    }
+
+    #[test]
+    fn filtering_node_does_not_stall_the_collector() {
+        // Regression test: a worker whose `process()` returns `None` for some items (a
+        // filtering node) must not leave a sequence-number hole that wedges the collector
+        // and the dispatcher's backpressure loop behind it forever.
+        let items: Vec<i32> = (0..500).collect();
+        let expected: Vec<i32> = items.iter().copied().filter(|v| v % 2 == 0).collect();
+        assert_eq!(run_parallel(FilterEvenNode, 4, items), expected);
+    }
+}
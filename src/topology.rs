@@ -0,0 +1,172 @@
+//! # Topology
+//!
+//! `NodeInstance` already pins its thread to a hand-specified `cpu_core` via `core_affinity`, but
+//! picking good core numbers is guesswork without knowing which cores actually share cache. This
+//! module adapts the pointer-chase latency probe from the `cache_test` bin (see
+//! `src/bin/cache_test.rs` for the full heuristic and commentary) into a short per-core probe: it
+//! pins the current thread to each candidate core in turn and times a pointer chase over a working
+//! set sized to spill past a private L2 but still fit comfortably in a shared L3.
+//!
+//! The probe only measures each core's *own* solo-access latency, not cross-core latency (e.g. a
+//! cache-line ping-pong between two specific cores), so it can only tell apart cores whose cache
+//! hierarchies genuinely perform differently — heterogeneous cores (P/E cores) or NUMA-remote
+//! sockets, where a cross-domain core measures a real, consistent latency jump. Cores whose probed
+//! latency falls within [`SIBLING_TOLERANCE`] of each other are grouped together on the assumption
+//! that they're interchangeable for placement purposes; on a uniform multi-core part with a single
+//! flat cache domain, every core will measure about the same latency and collapse into one group,
+//! which [`Topology::auto_place`] then can't meaningfully distinguish. Treat the grouping as "cores
+//! that are at least not obviously worse to share a queue with," not a precise L3-slice map.
+use log::warn;
+use std::hint::black_box;
+use std::sync::OnceLock;
+use std::thread;
+use std::time::Instant;
+
+/// Working-set size for the probe: large enough to spill a typical private L2 (256 KiB-1 MiB) but
+/// small enough to still land inside a shared L3 on most parts, so the measured latency reflects
+/// L3 access rather than DRAM.
+const PROBE_SIZE: usize = 4 * 1024 * 1024;
+
+/// Iterations to amortize timing overhead; far fewer than `cache_test`'s full sweep since this
+/// only needs to rank cores relative to each other, not produce an absolute latency curve.
+const PROBE_ITERATIONS: usize = 50_000;
+
+/// Two cores are considered to share a cache domain when their probed latencies are within this
+/// fraction of one another.
+const SIBLING_TOLERANCE: f64 = 0.15;
+
+/// Same permutation-based pointer chase as `cache_test::generate_pointer_chase`, defeating
+/// prefetchers while guaranteeing every element is visited exactly once.
+fn generate_pointer_chase(size: usize) -> Vec<usize> {
+    let n = size / std::mem::size_of::<usize>();
+    let mut chain = vec![0usize; n];
+
+    let mut indices: Vec<usize> = (0..n).collect();
+    let mut rng_state = 0xDEADBEEFu64;
+    for i in (1..n).rev() {
+        rng_state = rng_state.wrapping_mul(6364136223846793005).wrapping_add(1);
+        let j = (rng_state as usize) % (i + 1);
+        indices.swap(i, j);
+    }
+
+    for i in 0..n {
+        let current = indices[i];
+        let next = indices[(i + 1) % n];
+        chain[current] = next;
+    }
+
+    chain
+}
+
+/// Measure average nanoseconds/access by chasing pointers through `chain`, pinned to whichever
+/// core the caller already set affinity to.
+fn measure_latency(chain: &[usize], iterations: usize) -> f64 {
+    let ptr = chain.as_ptr();
+    let mut idx = 0usize;
+
+    for _ in 0..chain.len() {
+        idx = unsafe { *ptr.add(idx) };
+    }
+
+    let start = Instant::now();
+    for _ in 0..iterations {
+        idx = unsafe { *ptr.add(idx) };
+    }
+    let elapsed = start.elapsed();
+
+    black_box(idx);
+    elapsed.as_nanos() as f64 / iterations as f64
+}
+
+/// A set of logical core IDs inferred to share an L2/L3 cache domain.
+pub type CacheGroup = Vec<usize>;
+
+/// Discovered core topology: logical core IDs grouped by inferred shared cache domain, in
+/// descending order of group size so [`Topology::auto_place`] can prefer the roomiest group.
+pub struct Topology {
+    groups: Vec<CacheGroup>,
+}
+
+impl Topology {
+    /// Probe every logical core once and group the ones that appear to share cache. This walks
+    /// all cores serially (each probe pins the *current* thread), so it's meant to run once at
+    /// startup; see [`Topology::global`] to pay that cost exactly once per process. The probing
+    /// itself happens on a dedicated, throwaway thread rather than the caller's: `core_affinity`
+    /// has no way to read back a thread's prior affinity, so the only way to leave the caller
+    /// pinned exactly where it started is to never touch its affinity at all.
+    pub fn discover() -> Self {
+        let Some(core_ids) = core_affinity::get_core_ids() else {
+            warn!("Couldn't enumerate CPU cores for topology discovery (NOTE: this is expected on macOS)");
+            return Topology { groups: Vec::new() };
+        };
+
+        let core_count = core_ids.len();
+        let mut latencies: Vec<(usize, f64)> = thread::spawn(move || {
+            let chain = generate_pointer_chase(PROBE_SIZE);
+            let mut latencies = Vec::with_capacity(core_ids.len());
+            for core in &core_ids {
+                if !core_affinity::set_for_current(core_affinity::CoreId { id: core.id }) {
+                    continue;
+                }
+                latencies.push((core.id, measure_latency(&chain, PROBE_ITERATIONS)));
+            }
+            latencies
+        })
+        .join()
+        .expect("topology probe thread panicked");
+
+        // Group cores with similar probed latency, scanning in latency order so each group is a
+        // contiguous run within tolerance of its first (lowest-latency) member.
+        latencies.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+        let mut groups: Vec<(CacheGroup, f64)> = Vec::new();
+        for (core_id, latency) in latencies {
+            match groups.last_mut() {
+                Some((group, first_latency)) if latency <= *first_latency * (1.0 + SIBLING_TOLERANCE) => {
+                    group.push(core_id);
+                }
+                _ => groups.push((vec![core_id], latency)),
+            }
+        }
+
+        let mut groups: Vec<CacheGroup> = groups.into_iter().map(|(group, _)| group).collect();
+        groups.sort_by_key(|g| std::cmp::Reverse(g.len()));
+
+        if groups.len() == 1 && core_count > 1 {
+            warn!(
+                "Topology probe found no latency difference across {} cores; auto_place() will \
+                 treat them as one interchangeable group rather than finding real cache-sharing \
+                 siblings (expected on a uniform, single-cache-domain machine)",
+                core_count
+            );
+        }
+
+        Topology { groups }
+    }
+
+    /// Lazily discover the process-wide topology once and reuse it for every call, since the
+    /// probe itself takes a noticeable fraction of a second and the result doesn't change at
+    /// runtime.
+    pub fn global() -> &'static Topology {
+        static TOPOLOGY: OnceLock<Topology> = OnceLock::new();
+        TOPOLOGY.get_or_init(Topology::discover)
+    }
+
+    /// Suggest `count` core numbers for a chain of adjacent pipeline stages, preferring cores
+    /// from the same cache-sharing group so handing a buffer between them over its SPSC queue
+    /// stays on-chip. Falls back to spreading across whatever cores are left once a group is
+    /// exhausted. Returns fewer than `count` entries if topology discovery found fewer usable
+    /// cores than requested; callers should keep pinning explicit `cpu_core`s for any that don't
+    /// get an entry back.
+    pub fn auto_place(&self, count: usize) -> Vec<usize> {
+        let mut placement = Vec::with_capacity(count);
+        for group in &self.groups {
+            for &core_id in group {
+                if placement.len() == count {
+                    return placement;
+                }
+                placement.push(core_id);
+            }
+        }
+        placement
+    }
+}